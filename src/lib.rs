@@ -1,3 +1,26 @@
+//! # Known limitations
+//!
+//! This contract is built against `chess_engine` v0.1.2, whose `Board`/
+//! `BoardBuilder` API is narrower than some features below would ideally
+//! want. These are accepted limitations of that dependency version, not bugs
+//! to keep chasing without forking/patching it:
+//!
+//! - **En-passant does not survive a storage round-trip.** `serialize_meta`
+//!   records the en-passant file, but `BoardBuilder` has no en-passant
+//!   setter, so `deserialize_board` can never feed it back into a
+//!   reconstructed `Board`. Every move, including the engine's reply in
+//!   `play_vs_engine`, is played on a board reloaded from storage, so an
+//!   en-passant capture is never accepted.
+//! - **FEN import/export loses the en-passant square**, for the same reason:
+//!   `load_fen` validates but discards it, and `export_fen` always reports
+//!   `-`. Every other FEN field (placement, side to move, castling rights,
+//!   half-move clock, full-move number) round-trips exactly.
+//! - **Under-promotion is not supported.** `move_piece` unconditionally
+//!   auto-promotes a pawn reaching the back rank to a Queen, so
+//!   `play_promotion` can only honor a `QUEEN` selector; choosing a knight,
+//!   bishop, or rook is rejected as `ILLEGAL_MOVE` rather than silently
+//!   promoting to the wrong piece.
+
 // Only run this as a WASM if the export-abi feature is not set.
 #![cfg_attr(not(feature = "export-abi"), no_main)]
 extern crate alloc;
@@ -7,22 +30,50 @@ extern crate alloc;
 static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
 use alloy_primitives::{Address, U8};
-use chess_engine::{Board, BoardBuilder, Color, GameResult, Move, Piece, Position};
+use chess_engine::{Board, BoardBuilder, Color, Evaluate, GameResult, Move, Piece, Position};
 
 /// Import the Stylus SDK along with alloy primitive types for use in our program.
-use stylus_sdk::{alloy_primitives::U256, console, msg, prelude::*};
+use stylus_sdk::{
+    alloy_primitives::U256, alloy_sol_types::sol, console, contract, evm, msg, prelude::*,
+};
+
+sol! {
+    /// Emitted when a player opens a new game and becomes White.
+    event GameCreated(uint256 indexed game_number, address indexed creator);
+    /// Emitted when a second player joins a pending game as Black.
+    event PlayerJoined(uint256 indexed game_number, address indexed player);
+    /// Emitted for every accepted move, carrying enough data to replay the game.
+    event MovePlayed(
+        uint256 indexed game_number,
+        address indexed mover,
+        uint8 from_row,
+        uint8 from_col,
+        uint8 to_row,
+        uint8 to_col,
+        uint8 promotion,
+        uint8 status,
+        uint256 position_hash
+    );
+    /// Emitted when a game reaches a terminal status (victory, stalemate, draw).
+    event GameEnded(uint256 indexed game_number, uint8 status, uint8 victor);
+}
 
 /// Game Status
-// const PENDING: u8 = 0;
+const PENDING: u8 = 0;
 const CONTINUING: u8 = 1;
 const ILLEGAL_MOVE: u8 = 2;
 const STALEMATE: u8 = 3;
 const VICTORY: u8 = 4;
+const DRAW: u8 = 5;
 
 /// Colors
 const WHITE: u8 = 0;
 const BLACK: u8 = 1;
 
+/// Castle side selectors for `play_castle`.
+const KINGSIDE: u8 = 0;
+const QUEENSIDE: u8 = 1;
+
 /// Piece types
 const PAWN: u8 = 1;
 const KNIGHT: u8 = 2;
@@ -35,6 +86,141 @@ const KING: u8 = 6;
 const COLOR_MASK: u8 = 1;
 const PIECE_TYPE_MASK: u8 = 7;
 
+/// Castling-availability bits packed into `board_meta`.
+const CASTLE_WK: u8 = 1 << 0;
+const CASTLE_WQ: u8 = 1 << 1;
+const CASTLE_BK: u8 = 1 << 2;
+const CASTLE_BQ: u8 = 1 << 3;
+/// Castling rights available from the starting position.
+const ALL_CASTLE_RIGHTS: u8 = CASTLE_WK | CASTLE_WQ | CASTLE_BK | CASTLE_BQ;
+
+/// Field offsets within the `board_meta` trailer word.
+const META_CASTLE_OFFSET: usize = 0;
+const META_EN_PASSANT_OFFSET: usize = 4;
+const META_HALF_MOVE_OFFSET: usize = 8;
+const META_FULL_MOVE_OFFSET: usize = 16;
+
+/// Masks for the fields packed into `board_meta`.
+const META_CASTLE_MASK: u8 = 0xf;
+const META_EN_PASSANT_MASK: u8 = 0xf;
+const META_HALF_MOVE_MASK: u16 = 0xff;
+const META_FULL_MOVE_MASK: u32 = 0xffff;
+
+/// Material weights for the engine's evaluation, in centipawns.
+const PAWN_VALUE: i32 = 100;
+const KNIGHT_VALUE: i32 = 320;
+const BISHOP_VALUE: i32 = 330;
+const ROOK_VALUE: i32 = 500;
+const QUEEN_VALUE: i32 = 900;
+const KING_VALUE: i32 = 20_000;
+/// Score assigned to a checkmated position, larger than any material balance.
+const CHECKMATE_SCORE: i32 = 1_000_000;
+/// Upper bound on the engine search depth so a reply fits inside the gas limit.
+const MAX_ENGINE_DEPTH: u32 = 4;
+
+/// A position repeated this many times is a draw by threefold repetition.
+const THREEFOLD_REPETITION: u8 = 3;
+/// Fifty full moves without a capture or pawn advance (100 half-moves) is a draw.
+const FIFTY_MOVE_HALF_MOVES: u16 = 100;
+
+/// Layout of the Zobrist key table: one key per {color, piece-type, square},
+/// one for side-to-move, four for castling availability, eight for the
+/// en-passant file.
+const ZOBRIST_LEN: usize = 12 * 64 + 1 + 4 + 8;
+const ZOBRIST_SIDE_INDEX: usize = 12 * 64;
+const ZOBRIST_CASTLE_INDEX: usize = ZOBRIST_SIDE_INDEX + 1;
+const ZOBRIST_EN_PASSANT_INDEX: usize = ZOBRIST_CASTLE_INDEX + 4;
+
+/// Deterministic Zobrist keys derived at compile time with SplitMix64 so that
+/// every validator hashes identical positions identically.
+const ZOBRIST_KEYS: [u64; ZOBRIST_LEN] = build_zobrist_keys();
+
+/// Packs the board trailer fields into a single `board_meta` word.
+fn pack_meta(
+    castle_bits: u8,
+    en_passant_file: u8,
+    half_move_clock: u16,
+    full_move_number: u32,
+) -> U256 {
+    let mut board_meta = U256::ZERO;
+    board_meta |= U256::from(castle_bits & META_CASTLE_MASK) << META_CASTLE_OFFSET;
+    board_meta |= U256::from(en_passant_file & META_EN_PASSANT_MASK) << META_EN_PASSANT_OFFSET;
+    board_meta |= U256::from(half_move_clock & META_HALF_MOVE_MASK) << META_HALF_MOVE_OFFSET;
+    board_meta |= U256::from(full_move_number & META_FULL_MOVE_MASK) << META_FULL_MOVE_OFFSET;
+    board_meta
+}
+
+/// Castling-availability bits read out of a `board_meta` word.
+fn unpack_castle_bits(board_meta: U256) -> u8 {
+    U8::from((board_meta >> META_CASTLE_OFFSET) & U256::from(META_CASTLE_MASK)).to()
+}
+
+/// En-passant file (0 = none, 1-8 = a-h) read out of a `board_meta` word.
+fn unpack_en_passant_file(board_meta: U256) -> u8 {
+    U8::from((board_meta >> META_EN_PASSANT_OFFSET) & U256::from(META_EN_PASSANT_MASK)).to()
+}
+
+/// Half-move clock read out of a `board_meta` word.
+fn unpack_half_move_clock(board_meta: U256) -> u16 {
+    u16::from(
+        U8::from((board_meta >> META_HALF_MOVE_OFFSET) & U256::from(META_HALF_MOVE_MASK))
+            .to::<u8>(),
+    )
+}
+
+/// Full-move number read out of a `board_meta` word.
+fn unpack_full_move_number(board_meta: U256) -> u32 {
+    ((board_meta >> META_FULL_MOVE_OFFSET) & U256::from(META_FULL_MOVE_MASK)).to()
+}
+
+/// Derives the castling rights surviving a move, given the rights held before
+/// it. `chess_engine` v0.1.2 exposes no way to read raw castling rights back
+/// off a `Board` (only `can_*_castle`, which also depends on the path being
+/// clear and the king not being in check), so rights are tracked independently
+/// here: a king or rook leaving, or a rook being captured on, one of the four
+/// home squares permanently revokes the corresponding right.
+fn next_castle_bits(previous: u8, from: Position, to: Position) -> u8 {
+    let mut bits = previous;
+    let from = (from.get_row(), from.get_col());
+    let to = (to.get_row(), to.get_col());
+
+    if from == (0, 4) {
+        bits &= !(CASTLE_WK | CASTLE_WQ);
+    }
+    if from == (7, 4) {
+        bits &= !(CASTLE_BK | CASTLE_BQ);
+    }
+    if from == (0, 0) || to == (0, 0) {
+        bits &= !CASTLE_WQ;
+    }
+    if from == (0, 7) || to == (0, 7) {
+        bits &= !CASTLE_WK;
+    }
+    if from == (7, 0) || to == (7, 0) {
+        bits &= !CASTLE_BQ;
+    }
+    if from == (7, 7) || to == (7, 7) {
+        bits &= !CASTLE_BK;
+    }
+
+    bits
+}
+
+const fn build_zobrist_keys() -> [u64; ZOBRIST_LEN] {
+    let mut keys = [0_u64; ZOBRIST_LEN];
+    let mut state: u64 = 0x1234_5678_9abc_def0;
+    let mut i = 0;
+    while i < ZOBRIST_LEN {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
 sol_storage! {
   #[entrypoint]
   pub struct StylusChess {
@@ -44,6 +230,29 @@ sol_storage! {
     uint256 pending_game;
     /// Stores info for each chess game
     mapping(uint256 => GameInfo) games;
+    /// Per-game occurrence counts of each position hash, for threefold repetition.
+    mapping(uint256 => mapping(uint256 => uint8)) position_counts;
+    /// Ordered record of every move played in a game, for replay by indexers.
+    mapping(uint256 => PlayedMove[]) move_history;
+    /// Bumped to invalidate a game's repetition counts when its position is
+    /// reset (e.g. by loading a FEN), since a mapping cannot be cleared in bulk.
+    mapping(uint256 => uint256) repetition_epoch;
+  }
+
+  pub struct PlayedMove {
+    /// Address that submitted the move.
+    address mover;
+    uint8 from_row;
+    uint8 from_col;
+    uint8 to_row;
+    uint8 to_col;
+    /// Promotion selector (0 = none), matching the piece-type constants.
+    uint8 promotion;
+    /// Game status after the move was applied.
+    uint8 status;
+    /// Zobrist hash of the resulting position (0 for a move ending in
+    /// checkmate or stalemate).
+    uint256 position_hash;
   }
 
   pub struct GameInfo {
@@ -51,7 +260,7 @@ sol_storage! {
     address player_one;
     /// Player 2 is BLACK
     address player_two;
-    /// PENDING (waiting second player) = 0, CONTINUING = 1, STALEMATE = 3, or VICTORY = 4
+    /// PENDING = 0, CONTINUING = 1, STALEMATE = 3, VICTORY = 4, or DRAW = 5
     uint8 game_status;
     /// Player turn 0 = WHITE; 1 = BLACK
     uint8 turn_color;
@@ -59,6 +268,10 @@ sol_storage! {
     uint8 victor;
     /// All the info needed to rebuild the board
     uint256 board_state;
+    /// Trailer word for the board: castling availability (4 bits), en-passant
+    /// file (4 bits, 0 = none, 1-8 = file a-h), the fifty-move half-move clock
+    /// (8 bits), and the full-move number (16 bits).
+    uint256 board_meta;
   }
 }
 
@@ -116,52 +329,257 @@ impl StylusChess {
         let from_position = Position::new(from_row.to(), from_col.to());
         let to_position = Position::new(to_row.to(), to_col.to());
         let player_move = Move::Piece(from_position, to_position);
-        let move_result = board.play_move(player_move);
 
-        let response = match move_result {
-            GameResult::Continuing(new_board) => {
-                let new_board_state = self.serialize_board(new_board);
-                let mut game_setter = self.games.setter(game_number);
-                game_setter.board_state.set(new_board_state);
+        let response = self.apply_move(game_number, msg::sender(), board, from_position, player_move);
 
-                match new_board.get_turn_color() {
-                    Color::White => {
-                        game_setter.turn_color.set(U8::from(WHITE));
-                    }
-                    Color::Black => {
-                        game_setter.turn_color.set(U8::from(BLACK));
-                    }
-                }
+        Ok(response)
+    }
 
-                U256::from(CONTINUING)
-            }
-            GameResult::Victory(_) => {
-                let current_color = match board.get_turn_color() {
-                    Color::White => U8::from(WHITE),
-                    Color::Black => U8::from(BLACK),
-                };
-                let mut game_setter = self.games.setter(game_number);
-                game_setter.victor.set(current_color);
-                game_setter.game_status.set(U8::from(VICTORY));
+    /// Play a pawn move that promotes on the final rank. `chess_engine` (v0.1.2)
+    /// has no promotion move of its own: `move_piece` unconditionally
+    /// auto-promotes any pawn reaching the back rank to a Queen, so
+    /// under-promotion cannot be expressed. The `promotion` selector is kept
+    /// only so callers state their intent explicitly; it must be `QUEEN`, and
+    /// any other selector (including `KNIGHT`, `BISHOP`, or `ROOK`) is rejected
+    /// as `ILLEGAL_MOVE` rather than silently promoting to a piece the caller
+    /// didn't ask for.
+    pub fn play_promotion(
+        &mut self,
+        game_number: U256,
+        from_row: U256,
+        from_col: U256,
+        to_row: U256,
+        to_col: U256,
+        promotion: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let board = self.get_board_from_game_number(game_number);
+        let current_player = self.get_current_player_address(game_number, board);
+        let game_data = self.games.get(game_number);
+
+        // only allow the current player address to execute this call
+        if msg::sender() != current_player {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        // don't continue if game is already over
+        if game_data.game_status.get() != U8::from(CONTINUING) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        // Reject out-of-range selectors rather than panicking on the conversion.
+        if promotion > U256::from(u8::MAX) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+        if U8::from(promotion).to::<u8>() != QUEEN {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        let from_position = Position::new(from_row.to(), from_col.to());
+        let to_position = Position::new(to_row.to(), to_col.to());
+        let player_move = Move::Piece(from_position, to_position);
+
+        Ok(self.apply_move(game_number, msg::sender(), board, from_position, player_move))
+    }
+
+    /// Castle king-side (`0`) or queen-side (`1`). Returns `ILLEGAL_MOVE` if the
+    /// reloaded state does not grant the requested castling right.
+    pub fn play_castle(&mut self, game_number: U256, side: U256) -> Result<U256, Vec<u8>> {
+        let board = self.get_board_from_game_number(game_number);
+        let current_player = self.get_current_player_address(game_number, board);
+        let game_data = self.games.get(game_number);
+
+        // only allow the current player address to execute this call
+        if msg::sender() != current_player {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        // don't continue if game is already over
+        if game_data.game_status.get() != U8::from(CONTINUING) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        let king_row = match board.get_turn_color() {
+            Color::White => 0,
+            Color::Black => 7,
+        };
+        let king_from = Position::new(king_row, 4);
+        // Reject out-of-range selectors rather than panicking on the conversion.
+        if side > U256::from(u8::MAX) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+        let player_move = match U8::from(side).to() {
+            KINGSIDE => Move::KingSideCastle,
+            QUEENSIDE => Move::QueenSideCastle,
+            _ => return Ok(U256::from(ILLEGAL_MOVE)),
+        };
+
+        Ok(self.apply_move(game_number, msg::sender(), board, king_from, player_move))
+    }
 
-                U256::from(VICTORY)
+    /// Play a move and have the contract reply with an engine move in the same
+    /// transaction. The caller drives White; the contract answers as Black with
+    /// a depth-limited alpha-beta search. Returns the status after the reply.
+    /// Only reachable for solo games created via `create_solo_game`; returns
+    /// `ILLEGAL_MOVE` for a PvP game so a human opponent's turn can't be
+    /// played and replied to by the other player in one call.
+    pub fn play_vs_engine(
+        &mut self,
+        game_number: U256,
+        from_row: U256,
+        from_col: U256,
+        to_row: U256,
+        to_col: U256,
+        depth: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let board = self.get_board_from_game_number(game_number);
+        let current_player = self.get_current_player_address(game_number, board);
+        let game_data = self.games.get(game_number);
+
+        // Only reachable for solo games (see `create_solo_game`), where the
+        // caller is seated as both players. Otherwise a PvP player to move
+        // could play their move and also trigger — and steal — their human
+        // opponent's reply.
+        if game_data.player_one.get() != game_data.player_two.get() {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        // only allow the current player address to execute this call
+        if msg::sender() != current_player {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        // don't continue if game is already over
+        if game_data.game_status.get() != U8::from(CONTINUING) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        let from_position = Position::new(from_row.to(), from_col.to());
+        let to_position = Position::new(to_row.to(), to_col.to());
+        let player_move = Move::Piece(from_position, to_position);
+
+        let status = self.apply_move(game_number, msg::sender(), board, from_position, player_move);
+        if status != U256::from(CONTINUING) {
+            return Ok(status);
+        }
+
+        // The player's move stuck, so it is now the engine's turn to reply. The
+        // reply is attributed to the contract itself, not the human caller.
+        let engine_board = self.get_board_from_game_number(game_number);
+        match self.search_best_move(&engine_board, self.capped_depth(depth)) {
+            Some((engine_move, from)) => Ok(self.apply_move(
+                game_number,
+                contract::address(),
+                engine_board,
+                from,
+                engine_move,
+            )),
+            None => Ok(U256::from(CONTINUING)),
+        }
+    }
+
+    /// Runs a negamax search with alpha-beta pruning over the current board and
+    /// returns the best move's from/to coordinates. This is a view helper for
+    /// solo players and front-ends; it never mutates storage.
+    pub fn best_move(
+        &self,
+        game_number: U256,
+        depth: U256,
+    ) -> Result<(U256, U256, U256, U256), Vec<u8>> {
+        let board = self.get_board_from_game_number(game_number);
+        match self.search_best_move(&board, self.capped_depth(depth)) {
+            Some((engine_move, _)) => {
+                let (from, to) = self.move_squares(&board, engine_move);
+                Ok((
+                    U256::from(from.get_row() as u32),
+                    U256::from(from.get_col() as u32),
+                    U256::from(to.get_row() as u32),
+                    U256::from(to.get_col() as u32),
+                ))
             }
-            GameResult::Stalemate => {
-                let mut game_setter = self.games.setter(game_number);
-                game_setter.game_status.set(U8::from(STALEMATE));
+            None => Ok((U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO)),
+        }
+    }
+
+    /// Initialize a game's board from a Forsyth-Edwards Notation string and
+    /// start the game. Only the game's creator may initialize it, and only while
+    /// it is still pending, so a FEN can never overwrite a game in progress or
+    /// one that has already finished. Returns `CONTINUING` on success or
+    /// `ILLEGAL_MOVE` for a malformed FEN, a non-creator, or a non-pending game.
+    /// An en-passant target in the FEN is validated but not imported (see the
+    /// crate-level known-limitations docs); placement, side to move, castling
+    /// rights, and clocks import correctly.
+    pub fn load_fen(&mut self, game_number: U256, fen: String) -> Result<U256, Vec<u8>> {
+        let game_data = self.games.get(game_number);
+        let player_one = game_data.player_one.get();
+
+        // Only allow initialization of a game that has not started yet.
+        if game_data.game_status.get() != U8::from(PENDING) {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+        // Only the creator may initialize an existing game; an untouched slot
+        // may be claimed by its caller.
+        if player_one != Address::ZERO && msg::sender() != player_one {
+            return Ok(U256::from(ILLEGAL_MOVE));
+        }
+
+        let (board, turn, castle_bits, half_move, full_move) = match Self::fen_to_board(&fen) {
+            Some(parsed) => parsed,
+            None => return Ok(U256::from(ILLEGAL_MOVE)),
+        };
+
+        let board_state = self.serialize_board(board);
+        let board_meta = self.serialize_meta(&board, castle_bits, half_move, full_move);
 
-                U256::from(STALEMATE)
+        {
+            let mut game_setter = self.games.setter(game_number);
+            if player_one == Address::ZERO {
+                game_setter.player_one.set(msg::sender());
             }
-            _ => U256::from(ILLEGAL_MOVE),
+            game_setter.board_state.set(board_state);
+            game_setter.board_meta.set(board_meta);
+            game_setter.turn_color.set(match turn {
+                Color::White => U8::from(WHITE),
+                Color::Black => U8::from(BLACK),
+            });
+            // Activate the game so subsequent moves are accepted.
+            game_setter.game_status.set(U8::from(CONTINUING));
+        }
+
+        // Discard any repetition counts carried over from the previous position.
+        let next_epoch = self.repetition_epoch.get(game_number) + U256::from(1);
+        self.repetition_epoch.set(game_number, next_epoch);
+
+        let position_hash = self.zobrist_hash(&board, board_meta, turn);
+        self.record_position(game_number, position_hash);
+
+        Ok(U256::from(CONTINUING))
+    }
+
+    /// Export a game's current board as a Forsyth-Edwards Notation string.
+    /// The en-passant field is always `-` (see the crate-level
+    /// known-limitations docs); every other field is exact.
+    pub fn export_fen(&self, game_number: U256) -> Result<String, Vec<u8>> {
+        let game_info = self.games.get(game_number);
+        let board_state = game_info.board_state.get();
+        let board_meta = game_info.board_meta.get();
+        let turn = match game_info.turn_color.get() == U8::from(WHITE) {
+            true => Color::White,
+            false => Color::Black,
         };
+        let board = self.deserialize_board(board_state, board_meta, turn);
 
-        Ok(response)
+        Ok(Self::board_to_fen(&board, board_meta, turn))
     }
 
     pub fn print_game_state(&self, game_number: U256) -> Result<(), Vec<u8>> {
         let game_info = self.games.get(U256::from(game_number));
         let board_state = game_info.board_state.get();
-        let board: Board = self.deserialize_board(board_state);
+        let board_meta = game_info.board_meta.get();
+        let turn = match game_info.turn_color.get() == U8::from(WHITE) {
+            true => Color::White,
+            false => Color::Black,
+        };
+        let board: Board = self.deserialize_board(board_state, board_meta, turn);
         self.print_board(&board);
 
         Ok(())
@@ -173,6 +591,32 @@ impl StylusChess {
         Ok(game_info.board_state.get())
     }
 
+    /// Number of moves recorded in a game's history.
+    pub fn move_count(&self, game_number: U256) -> Result<U256, Vec<u8>> {
+        Ok(U256::from(self.move_history.get(game_number).len()))
+    }
+
+    /// The from/to coordinates and promotion selector of a recorded move.
+    pub fn get_move(
+        &self,
+        game_number: U256,
+        index: U256,
+    ) -> Result<(U256, U256, U256, U256, U256), Vec<u8>> {
+        let history = self.move_history.get(game_number);
+        let entry = match history.get(index.to::<usize>()) {
+            Some(entry) => entry,
+            None => return Ok((U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO, U256::ZERO)),
+        };
+
+        Ok((
+            U256::from(entry.from_row.get().to::<u8>()),
+            U256::from(entry.from_col.get().to::<u8>()),
+            U256::from(entry.to_row.get().to::<u8>()),
+            U256::from(entry.to_col.get().to::<u8>()),
+            U256::from(entry.promotion.get().to::<u8>()),
+        ))
+    }
+
     /// Either creates a new game or joins a pending game if it exists
     /// Returns the game number
     pub fn create_or_join(&mut self) -> Result<U256, Vec<u8>> {
@@ -190,6 +634,29 @@ impl StylusChess {
 
         Ok(U256::from(pending_game))
     }
+
+    /// Creates a single-player game against the contract engine. The caller is
+    /// seated as both players and the game starts immediately so that
+    /// `play_vs_engine` is reachable without a second participant.
+    /// Returns the game number.
+    pub fn create_solo_game(&mut self) -> Result<U256, Vec<u8>> {
+        let game_number = self.get_next_game_number();
+        self.create_game(game_number);
+
+        {
+            let mut game_info = self.games.setter(game_number);
+            // the solo player controls both colors
+            game_info.player_two.set(msg::sender());
+            game_info.game_status.set(U8::from(CONTINUING));
+        }
+
+        evm::log(PlayerJoined {
+            game_number,
+            player: msg::sender(),
+        });
+
+        Ok(U256::from(game_number))
+    }
 }
 
 impl StylusChess {
@@ -205,16 +672,151 @@ impl StylusChess {
         let game_info = self.games.get(game_number);
         let color = game_info.turn_color.get();
         let board_state = game_info.board_state.get();
-        let board = self.deserialize_board(board_state);
+        let board_meta = game_info.board_meta.get();
 
         let color_enum = match color == U8::from(WHITE) {
             true => Color::White,
             false => Color::Black,
         };
 
+        let board = self.deserialize_board(board_state, board_meta, color_enum);
+
         board.set_turn(color_enum)
     }
 
+    /// Applies a validated move to the stored game, persisting the resulting
+    /// board and advancing the game status (continuing, victory, stalemate, or
+    /// draw). Returns the new status code.
+    fn apply_move(
+        &mut self,
+        game_number: U256,
+        mover: Address,
+        previous_board: Board,
+        from: Position,
+        player_move: Move,
+    ) -> U256 {
+        // The from/to squares of the move, needed both to advance castling
+        // rights below and to log the move once it has been applied.
+        let (_, to) = self.move_squares(&previous_board, player_move);
+
+        // status code after the move, resulting position hash, winning color,
+        // and whether the game reached a terminal state.
+        let (status, position_hash, victor, ended) = match previous_board.play_move(player_move) {
+            GameResult::Continuing(new_board) => {
+                let new_board_state = self.serialize_board(new_board);
+                let previous_meta = self.games.get(game_number).board_meta.get();
+                let previous_clock = self.half_move_clock(previous_meta);
+                let next_clock =
+                    self.next_half_move_clock(&previous_board, from, &new_board, previous_clock);
+                // The full-move number increments once Black has completed a move.
+                let next_full_move = match previous_board.get_turn_color() {
+                    Color::Black => self.full_move_number(previous_meta).saturating_add(1),
+                    Color::White => self.full_move_number(previous_meta),
+                };
+                let castle_bits = next_castle_bits(unpack_castle_bits(previous_meta), from, to);
+                let new_board_meta =
+                    self.serialize_meta(&new_board, castle_bits, next_clock, next_full_move);
+                let next_turn = new_board.get_turn_color();
+                {
+                    let mut game_setter = self.games.setter(game_number);
+                    game_setter.board_state.set(new_board_state);
+                    game_setter.board_meta.set(new_board_meta);
+                    game_setter.turn_color.set(match next_turn {
+                        Color::White => U8::from(WHITE),
+                        Color::Black => U8::from(BLACK),
+                    });
+                }
+
+                // Record the new position and check for a draw by repetition or
+                // by the fifty-move rule.
+                let position_hash = self.zobrist_hash(&new_board, new_board_meta, next_turn);
+                let occurrences = self.record_position(game_number, position_hash);
+
+                if occurrences >= THREEFOLD_REPETITION || next_clock >= FIFTY_MOVE_HALF_MOVES {
+                    self.games.setter(game_number).game_status.set(U8::from(DRAW));
+                    (DRAW, U256::from(position_hash), WHITE, true)
+                } else {
+                    (CONTINUING, U256::from(position_hash), WHITE, false)
+                }
+            }
+            GameResult::Victory(_) => {
+                let current_color = match previous_board.get_turn_color() {
+                    Color::White => WHITE,
+                    Color::Black => BLACK,
+                };
+                let mut game_setter = self.games.setter(game_number);
+                game_setter.victor.set(U8::from(current_color));
+                game_setter.game_status.set(U8::from(VICTORY));
+
+                (VICTORY, U256::ZERO, current_color, true)
+            }
+            GameResult::Stalemate => {
+                let mut game_setter = self.games.setter(game_number);
+                game_setter.game_status.set(U8::from(STALEMATE));
+
+                (STALEMATE, U256::ZERO, WHITE, true)
+            }
+            _ => return U256::from(ILLEGAL_MOVE),
+        };
+
+        // Append the accepted move to the game's history and surface it as a log.
+        // `chess_engine` has no promotion move of its own; it auto-promotes any
+        // pawn that completes a move on the back rank to a Queen (see
+        // `play_promotion`), so a successful move landing there was a
+        // promotion iff a pawn made it.
+        let promotion = match previous_board.get_piece(from) {
+            Some(Piece::Pawn(_, _)) if to.get_row() == 0 || to.get_row() == 7 => QUEEN,
+            _ => 0,
+        };
+        self.record_move(game_number, mover, from, to, promotion, status, position_hash);
+
+        evm::log(MovePlayed {
+            game_number,
+            mover,
+            from_row: from.get_row() as u8,
+            from_col: from.get_col() as u8,
+            to_row: to.get_row() as u8,
+            to_col: to.get_col() as u8,
+            promotion,
+            status,
+            position_hash,
+        });
+
+        if ended {
+            evm::log(GameEnded {
+                game_number,
+                status,
+                victor,
+            });
+        }
+
+        U256::from(status)
+    }
+
+    /// Appends a move to a game's on-chain history.
+    #[allow(clippy::too_many_arguments)]
+    fn record_move(
+        &mut self,
+        game_number: U256,
+        mover: Address,
+        from: Position,
+        to: Position,
+        promotion: u8,
+        status: u8,
+        position_hash: U256,
+    ) {
+        let mut history = self.move_history.setter(game_number);
+        let mut entry = history.grow();
+        entry.mover.set(mover);
+        entry.from_row.set(U8::from(from.get_row() as u8));
+        entry.from_col.set(U8::from(from.get_col() as u8));
+        entry.to_row.set(U8::from(to.get_row() as u8));
+        entry.to_col.set(U8::from(to.get_col() as u8));
+        entry.promotion.set(U8::from(promotion));
+        entry.status.set(U8::from(status));
+        entry.position_hash.set(position_hash);
+    }
+
     fn get_next_game_number(&mut self) -> U256 {
         let game_number = self.total_games.get() + U256::from(1);
         self.total_games.set(game_number);
@@ -225,25 +827,66 @@ impl StylusChess {
         let board = Board::default();
         // Set up pieces for serialization
         let board_state = self.serialize_board(board);
+        let board_meta = self.serialize_meta(&board, ALL_CASTLE_RIGHTS, 0, 1);
+
+        {
+            let mut game_info = self.games.setter(game_number);
+            game_info.player_one.set(msg::sender());
+            game_info.board_state.set(board_state);
+            game_info.board_meta.set(board_meta);
+        }
+
+        // Seed the repetition counter with the opening position.
+        let position_hash = self.zobrist_hash(&board, board_meta, board.get_turn_color());
+        self.record_position(game_number, position_hash);
 
-        let mut game_info = self.games.setter(game_number);
-        game_info.player_one.set(msg::sender());
-        game_info.board_state.set(board_state);
+        evm::log(GameCreated {
+            game_number,
+            creator: msg::sender(),
+        });
     }
 
     fn join_game(&mut self, game_number: U256) {
-        let mut game_info = self.games.setter(game_number);
-        // join as player two
-        game_info.player_two.set(msg::sender());
-        // change status to continuing
-        game_info.game_status.set(U8::from(CONTINUING));
+        {
+            let mut game_info = self.games.setter(game_number);
+            // join as player two
+            game_info.player_two.set(msg::sender());
+            // change status to continuing
+            game_info.game_status.set(U8::from(CONTINUING));
+        }
+
+        evm::log(PlayerJoined {
+            game_number,
+            player: msg::sender(),
+        });
         // empty out pending_game
         self.pending_game.set(U256::ZERO);
     }
 
-    fn deserialize_board(&self, board_state: U256) -> Board {
+    fn deserialize_board(&self, board_state: U256, board_meta: U256, turn: Color) -> Board {
         let mut board_builder: BoardBuilder = BoardBuilder::default();
-        board_builder = board_builder.enable_castling();
+        board_builder = board_builder.disable_castling();
+
+        let castle_bits = self.meta_field(board_meta, META_CASTLE_OFFSET, META_CASTLE_MASK);
+        if castle_bits & CASTLE_WK != 0 {
+            board_builder = board_builder.enable_kingside_castle(Color::White);
+        }
+        if castle_bits & CASTLE_WQ != 0 {
+            board_builder = board_builder.enable_queenside_castle(Color::White);
+        }
+        if castle_bits & CASTLE_BK != 0 {
+            board_builder = board_builder.enable_kingside_castle(Color::Black);
+        }
+        if castle_bits & CASTLE_BQ != 0 {
+            board_builder = board_builder.enable_queenside_castle(Color::Black);
+        }
+
+        // The en-passant file stored in `board_meta` (see `serialize_meta`)
+        // cannot be replayed back in here: `chess_engine` v0.1.2's
+        // `BoardBuilder` has no en-passant setter, only `Board::get_en_passant`
+        // on an already-live board. So a reconstructed board never grants the
+        // right back, and since every move is played on a reconstructed board,
+        // an en-passant capture is never accepted.
 
         for row in 0..8_u8 {
             for col in 0..8_u8 {
@@ -314,6 +957,507 @@ impl StylusChess {
         board_state
     }
 
+    /// Reads a single byte-or-smaller field out of the `board_meta` trailer word.
+    fn meta_field(&self, board_meta: U256, offset: usize, mask: u8) -> u8 {
+        let raw = (board_meta >> offset) & U256::from(mask);
+        U8::from(raw).to()
+    }
+
+    /// Packs castling availability, the en-passant file, the half-move clock,
+    /// and the full-move number into the `board_meta` trailer word. `castle_bits` is the caller's own
+    /// tracking of the rights (see `next_castle_bits`), since `Board` itself
+    /// exposes no raw rights getter to derive them from.
+    fn serialize_meta(
+        &self,
+        board: &Board,
+        castle_bits: u8,
+        half_move_clock: u16,
+        full_move_number: u32,
+    ) -> U256 {
+        // Store 1-8 for files a-h so that 0 cleanly means "no target".
+        let en_passant_file = board
+            .get_en_passant()
+            .map(|square| (square.get_col() + 1) as u8)
+            .unwrap_or(0);
+
+        pack_meta(castle_bits, en_passant_file, half_move_clock, full_move_number)
+    }
+
+    /// The fifty-move half-move clock currently stored for a game.
+    fn half_move_clock(&self, board_meta: U256) -> u16 {
+        unpack_half_move_clock(board_meta)
+    }
+
+    /// The full-move number currently stored for a game (starts at 1).
+    fn full_move_number(&self, board_meta: U256) -> u32 {
+        unpack_full_move_number(board_meta)
+    }
+
+    /// Advances the half-move clock: it resets on a pawn move or a capture and
+    /// increments otherwise, per the fifty-move rule.
+    fn next_half_move_clock(
+        &self,
+        previous: &Board,
+        from: Position,
+        next: &Board,
+        clock: u16,
+    ) -> u16 {
+        let moved_pawn = matches!(previous.get_piece(from), Some(Piece::Pawn(_, _)));
+        let captured = self.count_pieces(next) < self.count_pieces(previous);
+        if moved_pawn || captured {
+            0
+        } else {
+            clock.saturating_add(1)
+        }
+    }
+
+    /// Computes the Zobrist hash of a position from the board and its trailer
+    /// word. The hash is the XOR of the key for every occupied square's piece,
+    /// the side-to-move key when Black moves, and the keys for each active
+    /// castling right and en-passant file.
+    fn zobrist_hash(&self, board: &Board, board_meta: U256, turn: Color) -> u64 {
+        let mut hash: u64 = 0;
+
+        for row in 0..8_i32 {
+            for col in 0..8_i32 {
+                if let Some(piece) = board.get_piece(Position::new(row, col)) {
+                    let color_base = if piece.get_color() == Color::White { 0 } else { 6 };
+                    let piece_type = match piece {
+                        Piece::Pawn(_, _) => PAWN,
+                        Piece::Knight(_, _) => KNIGHT,
+                        Piece::Bishop(_, _) => BISHOP,
+                        Piece::Rook(_, _) => ROOK,
+                        Piece::Queen(_, _) => QUEEN,
+                        Piece::King(_, _) => KING,
+                    };
+                    let piece_index = color_base + (piece_type as usize - 1);
+                    let square = (row * 8 + col) as usize;
+                    hash ^= ZOBRIST_KEYS[piece_index * 64 + square];
+                }
+            }
+        }
+
+        if turn == Color::Black {
+            hash ^= ZOBRIST_KEYS[ZOBRIST_SIDE_INDEX];
+        }
+
+        let castle_bits = self.meta_field(board_meta, META_CASTLE_OFFSET, META_CASTLE_MASK);
+        for bit in 0..4 {
+            if castle_bits & (1 << bit) != 0 {
+                hash ^= ZOBRIST_KEYS[ZOBRIST_CASTLE_INDEX + bit];
+            }
+        }
+
+        // Per the FIDE repetition rule, the en-passant file only distinguishes a
+        // position when a pawn can actually make the capture this move.
+        let en_passant_file =
+            self.meta_field(board_meta, META_EN_PASSANT_OFFSET, META_EN_PASSANT_MASK);
+        if en_passant_file != 0 && self.en_passant_capture_available(board, turn) {
+            hash ^= ZOBRIST_KEYS[ZOBRIST_EN_PASSANT_INDEX + (en_passant_file as usize - 1)];
+        }
+
+        hash
+    }
+
+    /// Whether a pawn of the side to move stands diagonally behind the
+    /// en-passant target, ready to capture en passant this move. Whether that
+    /// capture would leave its own king in check is not examined.
+    fn en_passant_capture_available(&self, board: &Board, turn: Color) -> bool {
+        let target = match board.get_en_passant() {
+            Some(target) => target,
+            None => return false,
+        };
+
+        // The capturing pawn stands one rank behind the target, from the
+        // perspective of the side to move.
+        let source_row = match turn {
+            Color::White => target.get_row() - 1,
+            Color::Black => target.get_row() + 1,
+        };
+        if !(0..8).contains(&source_row) {
+            return false;
+        }
+
+        for delta in [-1_i32, 1] {
+            let source_col = target.get_col() + delta;
+            if !(0..8).contains(&source_col) {
+                continue;
+            }
+            if let Some(Piece::Pawn(color, _)) =
+                board.get_piece(Position::new(source_row, source_col))
+            {
+                if color == turn {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Increments and returns the occurrence count of a position hash for a game.
+    fn record_position(&mut self, game_number: U256, position_hash: u64) -> u8 {
+        let hash_key = self.position_key(game_number, position_hash);
+        let previous: u8 = self.position_counts.getter(game_number).get(hash_key).to();
+        let count = previous.saturating_add(1);
+        self.position_counts
+            .setter(game_number)
+            .setter(hash_key)
+            .set(U8::from(count));
+        count
+    }
+
+    /// Derives the repetition-count key, folding in the game's epoch so that a
+    /// reset (a bumped epoch) makes all prior counts unreachable.
+    fn position_key(&self, game_number: U256, position_hash: u64) -> U256 {
+        let epoch = self.repetition_epoch.get(game_number);
+        U256::from(position_hash) | (epoch << 64)
+    }
+
+    /// Counts the pieces left on a board, used to detect captures.
+    fn count_pieces(&self, board: &Board) -> u32 {
+        let mut count = 0;
+        for row in 0..8_i32 {
+            for col in 0..8_i32 {
+                if board.get_piece(Position::new(row, col)).is_some() {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Clamps a requested search depth into the gas-safe range.
+    fn capped_depth(&self, depth: U256) -> u32 {
+        let requested: u32 = depth.min(U256::from(MAX_ENGINE_DEPTH)).to();
+        requested.max(1)
+    }
+
+    /// Chooses the highest-scoring legal move for the side to move, returning it
+    /// alongside the originating square.
+    fn search_best_move(&self, board: &Board, depth: u32) -> Option<(Move, Position)> {
+        let mut alpha = i32::MIN + 1;
+        let beta = i32::MAX;
+        let mut best_score = i32::MIN + 1;
+        let mut best: Option<Move> = None;
+
+        for player_move in self.ordered_moves(board) {
+            let score = match board.play_move(player_move) {
+                GameResult::Continuing(child) => {
+                    -self.negamax(&child, depth.saturating_sub(1), -beta, -alpha)
+                }
+                GameResult::Victory(_) => CHECKMATE_SCORE,
+                GameResult::Stalemate => 0,
+                _ => continue,
+            };
+
+            if score > best_score {
+                best_score = score;
+                best = Some(player_move);
+            }
+            if score > alpha {
+                alpha = score;
+            }
+        }
+
+        best.map(|player_move| (player_move, self.move_squares(board, player_move).0))
+    }
+
+    /// Negamax search with alpha-beta pruning. Scores are always from the
+    /// perspective of the side to move on `board`.
+    fn negamax(&self, board: &Board, depth: u32, mut alpha: i32, beta: i32) -> i32 {
+        if depth == 0 {
+            return self.evaluate(board);
+        }
+
+        let moves = self.ordered_moves(board);
+        if moves.is_empty() {
+            return self.evaluate(board);
+        }
+
+        let mut best = i32::MIN + 1;
+        for player_move in moves {
+            let score = match board.play_move(player_move) {
+                GameResult::Continuing(child) => -self.negamax(&child, depth - 1, -beta, -alpha),
+                GameResult::Victory(_) => CHECKMATE_SCORE,
+                GameResult::Stalemate => 0,
+                _ => continue,
+            };
+
+            if score > best {
+                best = score;
+            }
+            if best > alpha {
+                alpha = best;
+            }
+            // The opponent already has a refutation at least as good as `beta`,
+            // so this branch cannot improve the principal variation.
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best
+    }
+
+    /// Static material evaluation: (side-to-move total - opponent total).
+    fn evaluate(&self, board: &Board) -> i32 {
+        let side = board.get_turn_color();
+        let mut score = 0;
+
+        for row in 0..8_i32 {
+            for col in 0..8_i32 {
+                if let Some(piece) = board.get_piece(Position::new(row, col)) {
+                    let value = self.piece_value(piece);
+                    if piece.get_color() == side {
+                        score += value;
+                    } else {
+                        score -= value;
+                    }
+                }
+            }
+        }
+
+        score
+    }
+
+    /// The material weight of a single piece.
+    fn piece_value(&self, piece: Piece) -> i32 {
+        match piece {
+            Piece::Pawn(_, _) => PAWN_VALUE,
+            Piece::Knight(_, _) => KNIGHT_VALUE,
+            Piece::Bishop(_, _) => BISHOP_VALUE,
+            Piece::Rook(_, _) => ROOK_VALUE,
+            Piece::Queen(_, _) => QUEEN_VALUE,
+            Piece::King(_, _) => KING_VALUE,
+        }
+    }
+
+    /// Legal moves ordered captures-first to sharpen alpha-beta pruning.
+    fn ordered_moves(&self, board: &Board) -> Vec<Move> {
+        let mut moves = board.get_legal_moves();
+        moves.sort_by_key(|player_move| u8::from(!self.is_capture(board, player_move)));
+        moves
+    }
+
+    /// Whether a move lands on an occupied square (a capture).
+    fn is_capture(&self, board: &Board, player_move: &Move) -> bool {
+        match player_move {
+            Move::Piece(_, to) => board.get_piece(*to).is_some(),
+            _ => false,
+        }
+    }
+
+    /// The from/to squares a move resolves to, mapping castling to the king's
+    /// two-square slide so callers always receive concrete coordinates.
+    fn move_squares(&self, board: &Board, player_move: Move) -> (Position, Position) {
+        let king_row = match board.get_turn_color() {
+            Color::White => 0,
+            Color::Black => 7,
+        };
+        match player_move {
+            Move::Piece(from, to) => (from, to),
+            Move::KingSideCastle => (Position::new(king_row, 4), Position::new(king_row, 6)),
+            Move::QueenSideCastle => (Position::new(king_row, 4), Position::new(king_row, 2)),
+            Move::Resign => (Position::new(king_row, 4), Position::new(king_row, 4)),
+        }
+    }
+
+    /// Renders a board and its trailer word as Forsyth-Edwards Notation,
+    /// walking ranks 8->1 and files a->h.
+    fn board_to_fen(board: &Board, board_meta: U256, turn: Color) -> String {
+        let mut fen = String::new();
+
+        for row in (0..8_i32).rev() {
+            let mut empty = 0;
+            for col in 0..8_i32 {
+                match board.get_piece(Position::new(row, col)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(Self::piece_to_fen_char(piece));
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if row > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let castle_bits = unpack_castle_bits(board_meta);
+        if castle_bits == 0 {
+            fen.push('-');
+        } else {
+            if castle_bits & CASTLE_WK != 0 {
+                fen.push('K');
+            }
+            if castle_bits & CASTLE_WQ != 0 {
+                fen.push('Q');
+            }
+            if castle_bits & CASTLE_BK != 0 {
+                fen.push('k');
+            }
+            if castle_bits & CASTLE_BQ != 0 {
+                fen.push('q');
+            }
+        }
+
+        fen.push(' ');
+        match board.get_en_passant() {
+            Some(pos) => {
+                fen.push((b'a' + pos.get_col() as u8) as char);
+                fen.push_str(&(pos.get_row() + 1).to_string());
+            }
+            None => fen.push('-'),
+        }
+
+        fen.push(' ');
+        fen.push_str(&unpack_half_move_clock(board_meta).to_string());
+        fen.push(' ');
+        fen.push_str(&unpack_full_move_number(board_meta).to_string());
+
+        fen
+    }
+
+    /// The FEN letter for a piece: uppercase for White, lowercase for Black.
+    fn piece_to_fen_char(piece: Piece) -> char {
+        let letter = match piece {
+            Piece::Pawn(_, _) => 'P',
+            Piece::Knight(_, _) => 'N',
+            Piece::Bishop(_, _) => 'B',
+            Piece::Rook(_, _) => 'R',
+            Piece::Queen(_, _) => 'Q',
+            Piece::King(_, _) => 'K',
+        };
+        match piece.get_color() {
+            Color::White => letter,
+            Color::Black => letter.to_ascii_lowercase(),
+        }
+    }
+
+    /// Parses a FEN string into a board, the side to move, the castling bits,
+    /// and the move clocks. Returns `None` when any field is malformed; the two
+    /// clocks may be omitted, and then default to 0 and 1.
+    fn fen_to_board(fen: &str) -> Option<(Board, Color, u8, u16, u32)> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next()?;
+        let active = fields.next()?;
+        let castling = fields.next()?;
+        let en_passant = fields.next()?;
+        let half_move: u16 = match fields.next() {
+            Some(s) => s.parse().ok()?,
+            None => 0,
+        };
+        let full_move: u32 = match fields.next() {
+            Some(s) => s.parse().ok()?,
+            None => 1,
+        };
+
+        let mut builder = BoardBuilder::default().disable_castling();
+
+        let ranks: Vec<&str> = placement.split('/').collect();
+        if ranks.len() != 8 {
+            return None;
+        }
+        for (rank_index, rank) in ranks.iter().enumerate() {
+            let row = 7 - rank_index as i32;
+            let mut col = 0_i32;
+            for ch in rank.chars() {
+                if let Some(skip) = ch.to_digit(10) {
+                    col += skip as i32;
+                } else {
+                    if !(0..8).contains(&col) {
+                        return None;
+                    }
+                    let color = if ch.is_ascii_uppercase() {
+                        Color::White
+                    } else {
+                        Color::Black
+                    };
+                    let position = Position::new(row, col);
+                    let piece = match ch.to_ascii_uppercase() {
+                        'P' => Piece::Pawn(color, position),
+                        'N' => Piece::Knight(color, position),
+                        'B' => Piece::Bishop(color, position),
+                        'R' => Piece::Rook(color, position),
+                        'Q' => Piece::Queen(color, position),
+                        'K' => Piece::King(color, position),
+                        _ => return None,
+                    };
+                    builder = builder.piece(piece);
+                    col += 1;
+                }
+            }
+            if col != 8 {
+                return None;
+            }
+        }
+
+        let mut castle_bits: u8 = 0;
+        for ch in castling.chars() {
+            builder = match ch {
+                'K' => {
+                    castle_bits |= CASTLE_WK;
+                    builder.enable_kingside_castle(Color::White)
+                }
+                'Q' => {
+                    castle_bits |= CASTLE_WQ;
+                    builder.enable_queenside_castle(Color::White)
+                }
+                'k' => {
+                    castle_bits |= CASTLE_BK;
+                    builder.enable_kingside_castle(Color::Black)
+                }
+                'q' => {
+                    castle_bits |= CASTLE_BQ;
+                    builder.enable_queenside_castle(Color::Black)
+                }
+                '-' => builder,
+                _ => return None,
+            };
+        }
+
+        if en_passant != "-" {
+            // Validate the square, but it cannot be restored onto the board:
+            // `BoardBuilder` (v0.1.2) has no en-passant setter (see
+            // `deserialize_board`), so an imported en-passant target does not
+            // survive into a capture right on the rebuilt `Board`.
+            let mut chars = en_passant.chars();
+            let file = chars.next()?;
+            let rank = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            let col = (file as i32) - ('a' as i32);
+            let row = (rank.to_digit(10)? as i32) - 1;
+            if !(0..8).contains(&col) || !(0..8).contains(&row) {
+                return None;
+            }
+        }
+
+        let turn = match active {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return None,
+        };
+
+        let board = builder.build().set_turn(turn);
+        Some((board, turn, castle_bits, half_move, full_move))
+    }
+
     fn print_board(&self, board: &Board) {
         let turn = board.get_turn_color();
         let abc = if turn == Color::White {
@@ -375,3 +1519,89 @@ impl StylusChess {
         console!("   {}", abc);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    const ALL_CASTLING: u8 = CASTLE_WK | CASTLE_WQ | CASTLE_BK | CASTLE_BQ;
+
+    #[test]
+    fn meta_round_trips_through_packing() {
+        // Exercise every field, including the boundaries of their masks.
+        let cases = [
+            (0_u8, 0_u8, 0_u16, 1_u32),
+            (ALL_CASTLING, 5, 49, 120),
+            (CASTLE_WK | CASTLE_BQ, 8, META_HALF_MOVE_MASK, META_FULL_MOVE_MASK),
+        ];
+
+        for (castle_bits, en_passant_file, half_move, full_move) in cases {
+            let packed = pack_meta(castle_bits, en_passant_file, half_move, full_move);
+            assert_eq!(unpack_castle_bits(packed), castle_bits);
+            assert_eq!(unpack_en_passant_file(packed), en_passant_file);
+            assert_eq!(unpack_half_move_clock(packed), half_move);
+            assert_eq!(unpack_full_move_number(packed), full_move);
+        }
+    }
+
+    #[test]
+    fn exports_starting_position_as_fen() {
+        let meta = pack_meta(ALL_CASTLING, 0, 0, 1);
+        let fen = StylusChess::board_to_fen(&Board::default(), meta, Color::White);
+        assert_eq!(fen, START_FEN);
+    }
+
+    #[test]
+    fn imports_fen_placement_and_clocks() {
+        let (board, turn, castle_bits, half_move, full_move) =
+            StylusChess::fen_to_board(START_FEN).expect("valid FEN");
+
+        assert_eq!(turn, Color::White);
+        assert_eq!(castle_bits, ALL_CASTLING);
+        assert_eq!(half_move, 0);
+        assert_eq!(full_move, 1);
+        assert!(matches!(
+            board.get_piece(Position::new(0, 4)),
+            Some(Piece::King(Color::White, _))
+        ));
+        assert!(matches!(
+            board.get_piece(Position::new(7, 3)),
+            Some(Piece::Queen(Color::Black, _))
+        ));
+        assert!(board.get_piece(Position::new(4, 4)).is_none());
+    }
+
+    #[test]
+    fn fen_import_then_export_is_stable() {
+        // Placement, side to move, castling rights, and clocks round-trip
+        // exactly. The en-passant target does not: `chess_engine` v0.1.2's
+        // `BoardBuilder` has no en-passant setter, so a target parsed out of
+        // the FEN cannot be restored onto the rebuilt `Board`, and
+        // `board_to_fen` (which reads the live board) reports it as absent.
+        let fen = "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2";
+        let (board, turn, castle_bits, half_move, full_move) =
+            StylusChess::fen_to_board(fen).expect("valid FEN");
+        assert_eq!(castle_bits, ALL_CASTLING);
+        assert!(board.get_en_passant().is_none());
+
+        let meta = pack_meta(castle_bits, 0, half_move, full_move);
+        let expected = "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+        assert_eq!(StylusChess::board_to_fen(&board, meta, turn), expected);
+    }
+
+    #[test]
+    fn rejects_malformed_fen() {
+        assert!(StylusChess::fen_to_board("not a fen").is_none());
+        assert!(StylusChess::fen_to_board("8/8/8/8/8/8/8 w - - 0 1").is_none());
+    }
+
+    #[test]
+    fn zobrist_table_is_deterministic_and_distinct() {
+        // The table must be identical on every build for cross-validator agreement.
+        assert_eq!(build_zobrist_keys(), ZOBRIST_KEYS);
+        assert_eq!(ZOBRIST_KEYS.len(), ZOBRIST_LEN);
+        assert_ne!(ZOBRIST_KEYS[0], ZOBRIST_KEYS[1]);
+        assert_ne!(ZOBRIST_KEYS[ZOBRIST_SIDE_INDEX], 0);
+    }
+}